@@ -0,0 +1,185 @@
+//! In-app DevTools instrumentation, compiled only for debug builds.
+//!
+//! Installs a `tracing_subscriber::Layer` that bridges IPC command events
+//! into a bounded ring buffer so the VoidScript frontend can render its own
+//! inspector panel instead of relying on the native webview devtools alone.
+//! The layer skips events outside its own target inside `on_event` (it does
+//! not filter in `event_enabled`, which would disable those events for the
+//! whole stack), and is composed alongside an `fmt` layer on a shared
+//! registry, so normal debug logging from the rest of the process is
+//! unaffected. Everything in this module is compiled out of
+//! release builds: `init()` is only ever called behind
+//! `#[cfg(debug_assertions)]` in `run()`.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{plugin::TauriPlugin, Manager, Runtime};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::Registry;
+
+/// Maximum number of IPC events retained before the oldest are evicted.
+const MAX_EVENTS: usize = 512;
+
+/// `tracing` target that [`record_invocation`] emits on and [`IpcLayer`]
+/// listens for; keeps the bridge from picking up unrelated log events.
+const IPC_EVENT_TARGET: &str = "voidscript::ipc";
+
+#[derive(Clone, Serialize)]
+pub struct IpcEvent {
+    pub command: String,
+    pub args_size: usize,
+    pub duration_ms: u128,
+    pub success: bool,
+    pub error: Option<String>,
+    pub timestamp_ms: u128,
+}
+
+#[derive(Clone, Default)]
+pub struct DevtoolsState(Arc<Mutex<VecDeque<IpcEvent>>>);
+
+impl DevtoolsState {
+    fn record(&self, event: IpcEvent) {
+        let mut events = self.0.lock().unwrap();
+        if events.len() >= MAX_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    fn snapshot(&self) -> Vec<IpcEvent> {
+        self.0.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// Records an IPC invocation by emitting a `tracing` event on
+/// [`IPC_EVENT_TARGET`]. [`IpcLayer`], installed on the global default
+/// registry by [`init`], bridges this into the ring buffer backing
+/// `devtools_get_events`.
+pub fn record_invocation(command: &str, args_size: usize, duration_ms: u128, error: Option<&str>) {
+    tracing::event!(
+        target: IPC_EVENT_TARGET,
+        Level::INFO,
+        command,
+        args_size = args_size as u64,
+        duration_ms = duration_ms as u64,
+        success = error.is_none(),
+        error = error.unwrap_or_default(),
+    );
+}
+
+#[derive(Default)]
+struct IpcEventVisitor {
+    command: Option<String>,
+    args_size: usize,
+    duration_ms: u128,
+    success: bool,
+    error: Option<String>,
+}
+
+impl Visit for IpcEventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "command" => self.command = Some(value.to_string()),
+            "error" if !value.is_empty() => self.error = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "args_size" => self.args_size = value as usize,
+            "duration_ms" => self.duration_ms = value as u128,
+            _ => {}
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == "success" {
+            self.success = value;
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+/// A minimal `tracing_subscriber` layer whose sole job is turning IPC events
+/// emitted via [`record_invocation`] into entries in a [`DevtoolsState`] ring
+/// buffer. Events on other targets are skipped in `on_event` rather than in
+/// `event_enabled`: a `false` from `event_enabled` disables the event for the
+/// whole stack, sibling layers (e.g. `fmt`) included, whereas skipping it here
+/// leaves normal logging working.
+struct IpcLayer {
+    state: DevtoolsState,
+}
+
+impl<S: tracing::Subscriber> Layer<S> for IpcLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != IPC_EVENT_TARGET {
+            return;
+        }
+        let mut visitor = IpcEventVisitor::default();
+        event.record(&mut visitor);
+        let Some(command) = visitor.command else { return };
+
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+
+        self.state.record(IpcEvent {
+            command,
+            args_size: visitor.args_size,
+            duration_ms: visitor.duration_ms,
+            success: visitor.success,
+            error: visitor.error,
+            timestamp_ms,
+        });
+    }
+}
+
+#[tauri::command]
+fn devtools_get_events(state: tauri::State<DevtoolsState>) -> Vec<IpcEvent> {
+    state.snapshot()
+}
+
+#[tauri::command]
+fn devtools_clear(state: tauri::State<DevtoolsState>) {
+    state.clear();
+}
+
+/// Builds the DevTools plugin: installs the IPC bridge as a layer on a
+/// `Registry` (alongside an `fmt` layer for normal debug logging) and manages
+/// the ring buffer it feeds. Only ever register this behind
+/// `#[cfg(debug_assertions)]` in `run()` so it never ships to users.
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    let state = DevtoolsState::default();
+
+    // Installing a global subscriber can only succeed once per process; a
+    // failure here just means something else already installed one (e.g. a
+    // test harness), so the IPC bridge is silently skipped rather than
+    // panicking on startup.
+    let subscriber = Registry::default()
+        .with(IpcLayer {
+            state: state.clone(),
+        })
+        .with(tracing_subscriber::fmt::layer());
+    let _ = tracing::subscriber::set_global_default(subscriber);
+
+    tauri::plugin::Builder::new("devtools")
+        .invoke_handler(tauri::generate_handler![devtools_get_events, devtools_clear])
+        .setup(move |app, _api| {
+            app.manage(state);
+            Ok(())
+        })
+        .build()
+}
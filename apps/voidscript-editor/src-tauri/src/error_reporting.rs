@@ -0,0 +1,169 @@
+//! Unified crash/error trail across the Rust/JS boundary.
+//!
+//! Command panics are caught via `std::panic::catch_unwind` and turned into a
+//! structured error instead of crashing the app. JS-side exceptions reach the
+//! same trail through `report_frontend_error`, and both are appended to a
+//! rotating log file under the app's log directory so packaged users leave a
+//! record behind instead of a silent crash.
+
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Runtime};
+
+#[cfg(debug_assertions)]
+use crate::devtools;
+
+/// Maximum number of errors retained in memory for `get_recent_errors`.
+const MAX_ERRORS: usize = 256;
+
+/// Name of the rotating log file under the app's log directory.
+const LOG_FILE_NAME: &str = "errors.log";
+
+/// Size past which the log file is rotated to `errors.log.old`.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReportedError {
+    pub command: String,
+    pub message: String,
+    pub backtrace: String,
+    pub timestamp_ms: u128,
+}
+
+impl ReportedError {
+    /// Builds a [`ReportedError`] for an ordinary (non-panic) command
+    /// failure, e.g. a `WebviewWindowBuilder` error. There's no backtrace to
+    /// capture since nothing unwound.
+    pub fn from_message(command: &str, message: impl Into<String>) -> Self {
+        Self {
+            command: command.to_string(),
+            message: message.into(),
+            backtrace: String::new(),
+            timestamp_ms: now_ms(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ErrorLogState(Mutex<VecDeque<ReportedError>>);
+
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+fn log_path<R: Runtime>(app: &tauri::AppHandle<R>) -> Option<PathBuf> {
+    let dir = app.path().app_log_dir().ok()?;
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir.join(LOG_FILE_NAME))
+}
+
+fn append_to_log<R: Runtime>(app: &tauri::AppHandle<R>, error: &ReportedError) {
+    let Some(path) = log_path(app) else { return };
+
+    if let Ok(metadata) = std::fs::metadata(&path) {
+        if metadata.len() > MAX_LOG_BYTES {
+            let _ = std::fs::rename(&path, path.with_extension("log.old"));
+        }
+    }
+
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        if let Ok(line) = serde_json::to_string(error) {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+}
+
+/// Records a structured error: appends it to the rotating log and the
+/// in-memory ring buffer surfaced by `get_recent_errors`.
+fn record_error<R: Runtime>(app: &tauri::AppHandle<R>, error: ReportedError) {
+    append_to_log(app, &error);
+    if let Some(state) = app.try_state::<ErrorLogState>() {
+        let mut errors = state.0.lock().unwrap();
+        if errors.len() >= MAX_ERRORS {
+            errors.pop_front();
+        }
+        errors.push_back(error);
+    }
+}
+
+/// Runs `command` body `f`, catching any panic and turning it into a
+/// structured [`ReportedError`] instead of letting it unwind into Tauri.
+/// Every invocation — success or failure — is also timed and recorded into
+/// the debug-only DevTools ring buffer via [`devtools::record_invocation`].
+pub fn guard<R: Runtime, T>(
+    app: &tauri::AppHandle<R>,
+    command: &str,
+    args_size: usize,
+    f: impl FnOnce() -> T,
+) -> Result<T, ReportedError> {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let duration_ms = start.elapsed().as_millis();
+
+    match result {
+        Ok(value) => {
+            #[cfg(debug_assertions)]
+            devtools::record_invocation(command, args_size, duration_ms, None);
+            Ok(value)
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "command panicked".to_string());
+            let error = ReportedError {
+                command: command.to_string(),
+                message,
+                backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+                timestamp_ms: now_ms(),
+            };
+            record_error(app, error.clone());
+            #[cfg(debug_assertions)]
+            devtools::record_invocation(command, args_size, duration_ms, Some(&error.message));
+            Err(error)
+        }
+    }
+}
+
+/// Logs an error raised by the frontend (an uncaught JS exception, a
+/// rejected promise, ...) into the same trail as Rust-side command panics.
+#[tauri::command]
+pub fn report_frontend_error<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    message: String,
+    stack: Option<String>,
+) -> Result<(), ReportedError> {
+    let args_size = message.len() + stack.as_ref().map_or(0, String::len);
+    let inner_app = app.clone();
+    guard(&app, "report_frontend_error", args_size, move || {
+        let error = ReportedError {
+            command: "<frontend>".to_string(),
+            message,
+            backtrace: stack.unwrap_or_default(),
+            timestamp_ms: now_ms(),
+        };
+        record_error(&inner_app, error);
+    })
+}
+
+/// Returns the in-memory buffer of recent errors, most recent last.
+#[tauri::command]
+pub fn get_recent_errors<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    state: tauri::State<ErrorLogState>,
+) -> Result<Vec<ReportedError>, ReportedError> {
+    guard(&app, "get_recent_errors", 0, move || {
+        state.0.lock().unwrap().iter().cloned().collect()
+    })
+}
@@ -1,32 +1,75 @@
-use tauri::{Manager, WebviewWindow};
+#[cfg(debug_assertions)]
+mod devtools;
+mod error_reporting;
+mod protocol;
+mod shortcuts;
+mod windows;
+
+use tauri::{Manager, Runtime, WebviewWindow};
+use error_reporting::{get_recent_errors, report_frontend_error, ErrorLogState, ReportedError};
+use protocol::{clear_virtual_asset, set_virtual_asset};
+use shortcuts::{register_shortcut, unregister_shortcut, RegisteredShortcuts};
+use windows::{close_window, create_window, focus_window, list_windows};
 
 /// Toggle developer tools for the main window
 #[tauri::command]
-fn toggle_devtools(window: WebviewWindow) {
-    if window.is_devtools_open() {
-        window.close_devtools();
-    } else {
-        window.open_devtools();
-    }
+fn toggle_devtools<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    window: WebviewWindow<R>,
+) -> Result<(), ReportedError> {
+    error_reporting::guard(&app, "toggle_devtools", 0, move || {
+        if window.is_devtools_open() {
+            window.close_devtools();
+        } else {
+            window.open_devtools();
+        }
+    })
 }
 
 /// Reload the webview
 #[tauri::command]
-fn reload_webview(webview: WebviewWindow) {
-    let _ = webview.eval("window.location.reload()");
+fn reload_webview<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    webview: WebviewWindow<R>,
+) -> Result<(), ReportedError> {
+    error_reporting::guard(&app, "reload_webview", 0, move || {
+        let _ = webview.eval("window.location.reload()");
+    })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_process::init())
-        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
-        .invoke_handler(tauri::generate_handler![toggle_devtools, reload_webview])
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build());
+
+    let builder = protocol::register(builder, protocol::workspace_root());
+
+    #[cfg(debug_assertions)]
+    let builder = builder.plugin(devtools::init());
+
+    builder
+        .invoke_handler(tauri::generate_handler![
+            toggle_devtools,
+            reload_webview,
+            create_window,
+            close_window,
+            focus_window,
+            list_windows,
+            register_shortcut,
+            unregister_shortcut,
+            report_frontend_error,
+            get_recent_errors,
+            set_virtual_asset,
+            clear_virtual_asset
+        ])
+        .manage(RegisteredShortcuts::default())
+        .manage(ErrorLogState::default())
         .setup(|app| {
             if cfg!(debug_assertions) {
                 // Auto-open devtools in debug mode
@@ -36,6 +79,15 @@ pub fn run() {
             }
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if !matches!(event, tauri::WindowEvent::Destroyed) {
+                return;
+            }
+            windows::emit_window_closed(&window.app_handle().clone(), window.label());
+            if window.label() == "main" {
+                shortcuts::unregister_all(&window.app_handle().clone());
+            }
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
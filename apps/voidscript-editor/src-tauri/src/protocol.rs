@@ -0,0 +1,389 @@
+//! Custom `void://` URI scheme handler.
+//!
+//! Serves editor documents, sandboxed workspace files, and generated HTML
+//! previews without exposing raw `file://` access to the webview. An
+//! in-memory layer of virtual assets (unsaved documents, generated previews)
+//! is consulted first; anything else is resolved relative to a configured
+//! root and rejected if it would escape that root.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use http::{header, HeaderValue, Response, StatusCode};
+use tauri::http::Request;
+use tauri::{Manager, Runtime, UriSchemeContext};
+
+use crate::error_reporting::{self, ReportedError};
+
+/// Scheme registered with `register_uri_scheme_protocol`.
+pub const SCHEME: &str = "void";
+
+struct VirtualAsset {
+    contents: Vec<u8>,
+    mime: String,
+}
+
+/// Resolves `void://` requests, first against an in-memory table of virtual
+/// assets and then against files under a single sandboxed root.
+pub struct VirtualFs {
+    root: PathBuf,
+    assets: Mutex<HashMap<String, VirtualAsset>>,
+}
+
+impl VirtualFs {
+    /// Canonicalizes `root` up front so later prefix checks in [`resolve`]
+    /// compare like-for-like paths; without this, a symlinked root component
+    /// would make every in-root request fail the `starts_with` check.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let root = root.canonicalize().unwrap_or(root);
+        Self {
+            root,
+            assets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn normalize(path: &str) -> String {
+        path.trim_start_matches('/').to_string()
+    }
+
+    /// Registers (or overwrites) an in-memory asset served at `path` ahead of
+    /// the sandboxed filesystem. Used for unsaved editor documents and
+    /// generated HTML previews that have no on-disk representation.
+    pub fn set_asset(&self, path: &str, contents: Vec<u8>, mime: impl Into<String>) {
+        self.assets.lock().unwrap().insert(
+            Self::normalize(path),
+            VirtualAsset {
+                contents,
+                mime: mime.into(),
+            },
+        );
+    }
+
+    /// Removes a previously registered in-memory asset, if any.
+    pub fn remove_asset(&self, path: &str) {
+        self.assets.lock().unwrap().remove(&Self::normalize(path));
+    }
+
+    /// Resolves a request path to an absolute path under `root`, rejecting
+    /// any path that would escape it (e.g. via `..` components).
+    fn resolve(&self, request_path: &str) -> Option<PathBuf> {
+        let relative = request_path.trim_start_matches('/');
+        let mut resolved = self.root.clone();
+        for component in Path::new(relative).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => {}
+                _ => return None,
+            }
+        }
+        resolved.canonicalize().ok().filter(|p| p.starts_with(&self.root))
+    }
+
+    pub fn handle(&self, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+        let path = request.uri().path();
+        let range = request.headers().get(header::RANGE);
+
+        if let Some(asset) = self.assets.lock().unwrap().get(&Self::normalize(path)) {
+            return respond(&asset.contents, &asset.mime, range);
+        }
+
+        let Some(file_path) = self.resolve(path) else {
+            return not_found();
+        };
+        let Ok(contents) = fs::read(&file_path) else {
+            return not_found();
+        };
+        let mime = mime_guess::from_path(&file_path)
+            .first_or_octet_stream()
+            .to_string();
+        respond(&contents, &mime, range)
+    }
+}
+
+/// Builds the response for `contents`, honoring a `Range` header if present.
+/// An unparseable `Range` is ignored per RFC 7233 (full body, `200`); a
+/// well-formed but out-of-bounds range gets a `416`.
+fn respond(contents: &[u8], mime: &str, range: Option<&HeaderValue>) -> Response<Vec<u8>> {
+    if let Some(range) = range {
+        if let Ok(range_str) = range.to_str() {
+            match serve_range(contents, mime, range_str) {
+                RangeOutcome::Satisfied(response) => return response,
+                RangeOutcome::Unsatisfiable => return range_not_satisfiable(contents.len()),
+                RangeOutcome::Invalid => {}
+            }
+        }
+    }
+    full_response(contents, mime)
+}
+
+fn full_response(contents: &[u8], mime: &str) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, mime)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::CONTENT_LENGTH, contents.len())
+        .body(contents.to_vec())
+        .unwrap()
+}
+
+enum RangeOutcome {
+    Satisfied(Response<Vec<u8>>),
+    /// Syntactically valid `Range` that can't be satisfied against the
+    /// resource's actual length (`416`).
+    Unsatisfiable,
+    /// Header doesn't parse as a `bytes=start-end` range at all; per RFC
+    /// 7233 this must be ignored, not treated as unsatisfiable.
+    Invalid,
+}
+
+/// Parses and serves a `Range: bytes=start-end` request against an in-memory
+/// buffer.
+fn serve_range(contents: &[u8], mime: &str, range_header: &str) -> RangeOutcome {
+    let Some(spec) = range_header.strip_prefix("bytes=") else {
+        return RangeOutcome::Invalid;
+    };
+    let Some((start_str, end_str)) = spec.split_once('-') else {
+        return RangeOutcome::Invalid;
+    };
+    let len = contents.len();
+    let Ok(start) = start_str.parse::<usize>() else {
+        return RangeOutcome::Invalid;
+    };
+    let end: usize = if end_str.is_empty() {
+        len.saturating_sub(1)
+    } else {
+        match end_str.parse() {
+            Ok(end) => end,
+            Err(_) => return RangeOutcome::Invalid,
+        }
+    };
+    if start > end || end >= len {
+        return RangeOutcome::Unsatisfiable;
+    }
+
+    let chunk = contents[start..=end].to_vec();
+    RangeOutcome::Satisfied(
+        Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_TYPE, mime)
+            .header(header::ACCEPT_RANGES, "bytes")
+            .header(header::CONTENT_LENGTH, chunk.len())
+            .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/{len}"))
+            .body(chunk)
+            .unwrap(),
+    )
+}
+
+fn not_found() -> Response<Vec<u8>> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Vec::new())
+        .unwrap()
+}
+
+/// Built when a `Range` header can't be satisfied against the resource's
+/// actual length, per RFC 7233 instead of silently serving the full body.
+fn range_not_satisfiable(len: usize) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(StatusCode::RANGE_NOT_SATISFIABLE)
+        .header(header::CONTENT_RANGE, format!("bytes */{len}"))
+        .body(Vec::new())
+        .unwrap()
+}
+
+/// Name of the environment variable used to configure the sandbox root for
+/// the `void://` protocol. Falls back to the current working directory when
+/// unset, which only really makes sense for local development.
+const WORKSPACE_ROOT_ENV: &str = "VOIDSCRIPT_WORKSPACE_ROOT";
+
+/// Resolves the configurable sandbox root: `VOIDSCRIPT_WORKSPACE_ROOT` when
+/// set, otherwise the current working directory.
+pub fn workspace_root() -> PathBuf {
+    std::env::var_os(WORKSPACE_ROOT_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::current_dir().expect("failed to resolve workspace root"))
+}
+
+/// Registers an unsaved document or generated preview as a virtual asset so
+/// it's servable over `void://` without ever touching disk.
+#[tauri::command]
+pub fn set_virtual_asset<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    path: String,
+    contents: Vec<u8>,
+    mime: Option<String>,
+) -> Result<(), ReportedError> {
+    let args_size = path.len() + contents.len();
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "set_virtual_asset", args_size, move || {
+        let mime = mime.unwrap_or_else(|| {
+            mime_guess::from_path(&path).first_or_octet_stream().to_string()
+        });
+        inner_app.state::<Arc<VirtualFs>>().set_asset(&path, contents, mime);
+    })
+}
+
+/// Removes a previously registered virtual asset.
+#[tauri::command]
+pub fn clear_virtual_asset<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    path: String,
+) -> Result<(), ReportedError> {
+    let args_size = path.len();
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "clear_virtual_asset", args_size, move || {
+        inner_app.state::<Arc<VirtualFs>>().remove_asset(&path);
+    })
+}
+
+/// Registers the `void://` protocol against `workspace_root` and manages the
+/// [`VirtualFs`] as app state so `set_virtual_asset`/`clear_virtual_asset`
+/// can reach the same instance the protocol handler reads from.
+pub fn register<R: Runtime>(
+    builder: tauri::Builder<R>,
+    workspace_root: impl Into<PathBuf>,
+) -> tauri::Builder<R> {
+    let fs = Arc::new(VirtualFs::new(workspace_root));
+    let protocol_fs = fs.clone();
+    builder
+        .manage(fs)
+        .register_uri_scheme_protocol(SCHEME, move |_ctx: UriSchemeContext<R>, request| {
+            protocol_fs.handle(&request)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("voidscript-protocol-test-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolve_rejects_parent_escape() {
+        let root = temp_root("escape");
+        fs::write(root.join("inside.txt"), b"inside").unwrap();
+        let vfs = VirtualFs::new(&root);
+
+        assert!(vfs.resolve("/../escape.txt").is_none());
+        assert!(vfs.resolve("/../../etc/passwd").is_none());
+    }
+
+    #[test]
+    fn resolve_rejects_absolute_escape() {
+        let root = temp_root("absolute");
+        let vfs = VirtualFs::new(&root);
+
+        // An absolute-looking component is just another path segment once
+        // joined onto `root`, and must still resolve to a missing file
+        // rather than escaping to the real filesystem root.
+        assert!(vfs.resolve("/etc/passwd").is_none());
+    }
+
+    #[test]
+    fn resolve_rejects_symlink_escape() {
+        let root = temp_root("symlink-root");
+        let outside = temp_root("symlink-outside");
+        fs::write(outside.join("secret.txt"), b"secret").unwrap();
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+        #[cfg(not(unix))]
+        return;
+
+        let vfs = VirtualFs::new(&root);
+        assert!(vfs.resolve("/escape/secret.txt").is_none());
+    }
+
+    #[test]
+    fn resolve_accepts_in_root_file() {
+        let root = temp_root("accept");
+        fs::write(root.join("doc.html"), b"<html></html>").unwrap();
+        let vfs = VirtualFs::new(&root);
+
+        let resolved = vfs.resolve("/doc.html").expect("should resolve in-root file");
+        assert_eq!(resolved, root.canonicalize().unwrap().join("doc.html"));
+    }
+
+    #[test]
+    fn virtual_asset_is_served_before_disk() {
+        let root = temp_root("virtual-priority");
+        fs::write(root.join("preview.html"), b"on disk").unwrap();
+        let vfs = VirtualFs::new(&root);
+        vfs.set_asset("/preview.html", b"in memory".to_vec(), "text/html");
+
+        let request = Request::builder()
+            .uri("void://localhost/preview.html")
+            .body(Vec::new())
+            .unwrap();
+        let response = vfs.handle(&request);
+        assert_eq!(response.body(), b"in memory");
+    }
+
+    #[test]
+    fn serve_range_rejects_start_after_end() {
+        let contents = b"0123456789";
+        assert!(matches!(
+            serve_range(contents, "text/plain", "bytes=5-2"),
+            RangeOutcome::Unsatisfiable
+        ));
+    }
+
+    #[test]
+    fn serve_range_rejects_end_past_length() {
+        let contents = b"0123456789";
+        assert!(matches!(
+            serve_range(contents, "text/plain", "bytes=0-100"),
+            RangeOutcome::Unsatisfiable
+        ));
+    }
+
+    #[test]
+    fn serve_range_supports_open_ended_range() {
+        let contents = b"0123456789";
+        match serve_range(contents, "text/plain", "bytes=5-") {
+            RangeOutcome::Satisfied(response) => {
+                assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+                assert_eq!(response.body(), b"56789");
+            }
+            _ => panic!("expected a satisfied range"),
+        }
+    }
+
+    #[test]
+    fn serve_range_treats_malformed_header_as_invalid() {
+        let contents = b"0123456789";
+        assert!(matches!(
+            serve_range(contents, "text/plain", "not-a-range"),
+            RangeOutcome::Invalid
+        ));
+        assert!(matches!(
+            serve_range(contents, "text/plain", "bytes=abc-5"),
+            RangeOutcome::Invalid
+        ));
+    }
+
+    #[test]
+    fn respond_falls_back_to_full_body_on_invalid_range() {
+        let contents = b"0123456789".to_vec();
+        let invalid = HeaderValue::from_static("not-a-range");
+        let response = respond(&contents, "text/plain", Some(&invalid));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), &contents);
+    }
+
+    #[test]
+    fn respond_returns_416_for_unsatisfiable_range() {
+        let contents = b"0123456789".to_vec();
+        let unsatisfiable = HeaderValue::from_static("bytes=100-200");
+        let response = respond(&contents, "text/plain", Some(&unsatisfiable));
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    }
+}
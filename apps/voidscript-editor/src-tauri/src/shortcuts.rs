@@ -0,0 +1,98 @@
+//! Frontend-driven global shortcut registration.
+//!
+//! Scripts register accelerators (e.g. `"CmdOrCtrl+Shift+P"`) against an
+//! arbitrary `action_id`. When the shortcut fires, a `global-shortcut` event
+//! carrying that `action_id` is emitted to the webview. Registrations are
+//! kept in managed state, from which `unregister_all` unregisters and clears
+//! them on app exit. No command lists the registered shortcuts.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::{Emitter, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+use crate::error_reporting::{self, ReportedError};
+
+/// Accelerator -> action_id registered by the frontend.
+#[derive(Default)]
+pub struct RegisteredShortcuts(Mutex<HashMap<String, String>>);
+
+/// Registers `accelerator` and associates it with `action_id`. Firing the
+/// shortcut emits a `global-shortcut` event whose payload is the action id.
+#[tauri::command]
+pub fn register_shortcut<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    accelerator: String,
+    action_id: String,
+) -> Result<(), ReportedError> {
+    let args_size = accelerator.len() + action_id.len();
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "register_shortcut", args_size, move || {
+        let app_handle = inner_app.clone();
+        // Captured by move rather than looked up by `shortcut.to_string()`: the
+        // plugin's `Shortcut` Display form is a normalized modifiers/key-code
+        // representation that does not round-trip the original accelerator
+        // string, so a map keyed by the raw accelerator would never match it.
+        let action_id_for_callback = action_id.clone();
+        inner_app
+            .global_shortcut()
+            .on_shortcut(accelerator.as_str(), move |_app, _shortcut, event| {
+                if event.state() != ShortcutState::Pressed {
+                    return;
+                }
+                let _ = app_handle.emit("global-shortcut", action_id_for_callback.clone());
+            })
+            .map_err(|err| ReportedError::from_message("register_shortcut", err.to_string()))?;
+
+        // Only recorded once registration actually succeeds, so a failed
+        // registration never leaves a stale accelerator in state.
+        inner_app
+            .state::<RegisteredShortcuts>()
+            .0
+            .lock()
+            .unwrap()
+            .insert(accelerator, action_id);
+        Ok(())
+    })?
+}
+
+/// Unregisters a previously registered accelerator.
+#[tauri::command]
+pub fn unregister_shortcut<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    accelerator: String,
+) -> Result<(), ReportedError> {
+    let args_size = accelerator.len();
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "unregister_shortcut", args_size, move || {
+        inner_app
+            .global_shortcut()
+            .unregister(accelerator.as_str())
+            .map_err(|err| ReportedError::from_message("unregister_shortcut", err.to_string()))?;
+        inner_app
+            .state::<RegisteredShortcuts>()
+            .0
+            .lock()
+            .unwrap()
+            .remove(&accelerator);
+        Ok(())
+    })?
+}
+
+/// Unregisters every shortcut currently tracked in state. Call this on app
+/// exit so nothing is left registered with the OS.
+pub fn unregister_all<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let accelerators: Vec<String> = app
+        .state::<RegisteredShortcuts>()
+        .0
+        .lock()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect();
+    for accelerator in accelerators {
+        let _ = app.global_shortcut().unregister(accelerator.as_str());
+    }
+    app.state::<RegisteredShortcuts>().0.lock().unwrap().clear();
+}
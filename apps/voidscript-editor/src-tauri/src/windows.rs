@@ -0,0 +1,128 @@
+//! Frontend-facing commands for spawning and controlling secondary webview
+//! windows (detached output panels, doc viewers, secondary editors, ...).
+
+use serde::Deserialize;
+use tauri::{Emitter, Manager, Runtime, Url, WebviewUrl, WebviewWindowBuilder};
+
+use crate::error_reporting::{self, ReportedError};
+
+/// Builds the `WebviewUrl` for `url`: an absolute URL (e.g. a doc viewer
+/// pointing at `https://...`) is loaded as an external page, anything else is
+/// resolved relative to the app's own bundled assets.
+fn resolve_window_url(url: &str) -> WebviewUrl {
+    match Url::parse(url) {
+        Ok(parsed) => WebviewUrl::External(parsed),
+        Err(_) => WebviewUrl::App(url.into()),
+    }
+}
+
+/// Options accepted from the frontend when creating a new window. Mirrors the
+/// subset of `WebviewWindowBuilder` we want scripts to be able to control.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowOptions {
+    pub title: Option<String>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub min_width: Option<f64>,
+    pub min_height: Option<f64>,
+    #[serde(default = "default_decorations")]
+    pub decorations: bool,
+    #[serde(default)]
+    pub always_on_top: bool,
+    #[serde(default)]
+    pub visible_on_all_workspaces: bool,
+}
+
+/// Windows are decorated unless the caller explicitly opts out.
+fn default_decorations() -> bool {
+    true
+}
+
+/// Creates a new webview window with the given `label` pointing at `url`.
+#[tauri::command]
+pub fn create_window<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    label: String,
+    url: String,
+    options: WindowOptions,
+) -> Result<(), ReportedError> {
+    let args_size = label.len() + url.len();
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "create_window", args_size, move || {
+        let mut builder = WebviewWindowBuilder::new(&inner_app, &label, resolve_window_url(&url))
+            .decorations(options.decorations)
+            .always_on_top(options.always_on_top)
+            .visible_on_all_workspaces(options.visible_on_all_workspaces);
+
+        if let Some(title) = options.title {
+            builder = builder.title(title);
+        }
+        if let (Some(width), Some(height)) = (options.width, options.height) {
+            builder = builder.inner_size(width, height);
+        }
+        if let (Some(width), Some(height)) = (options.min_width, options.min_height) {
+            builder = builder.min_inner_size(width, height);
+        }
+
+        builder
+            .build()
+            .map(|_| ())
+            .map_err(|err| ReportedError::from_message("create_window", err.to_string()))
+    })?
+}
+
+/// Closes the window identified by `label`. `window-closed` is emitted for
+/// every window teardown (see `on_window_event` in `run()`), whether it was
+/// closed through this command or by the user clicking the OS close button.
+#[tauri::command]
+pub fn close_window<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    label: String,
+) -> Result<(), ReportedError> {
+    let args_size = label.len();
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "close_window", args_size, move || {
+        if let Some(window) = inner_app.get_webview_window(&label) {
+            window
+                .close()
+                .map_err(|err| ReportedError::from_message("close_window", err.to_string()))?;
+        }
+        Ok(())
+    })?
+}
+
+/// Emits `window-closed` for `label` so the frontend can clean up any state
+/// associated with it. Called from `on_window_event` on `WindowEvent::Destroyed`
+/// so it fires regardless of whether the window was closed via `close_window`
+/// or by the user.
+pub fn emit_window_closed<R: Runtime>(app: &tauri::AppHandle<R>, label: &str) {
+    let _ = app.emit("window-closed", label);
+}
+
+/// Focuses the window identified by `label`.
+#[tauri::command]
+pub fn focus_window<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    label: String,
+) -> Result<(), ReportedError> {
+    let args_size = label.len();
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "focus_window", args_size, move || {
+        if let Some(window) = inner_app.get_webview_window(&label) {
+            window
+                .set_focus()
+                .map_err(|err| ReportedError::from_message("focus_window", err.to_string()))?;
+        }
+        Ok(())
+    })?
+}
+
+/// Lists the labels of every currently open webview window.
+#[tauri::command]
+pub fn list_windows<R: Runtime>(app: tauri::AppHandle<R>) -> Result<Vec<String>, ReportedError> {
+    let inner_app = app.clone();
+    error_reporting::guard(&app, "list_windows", 0, move || {
+        inner_app.webview_windows().keys().cloned().collect()
+    })
+}